@@ -81,6 +81,19 @@ enum Commands {
 
         #[arg(long, default_value_t = 32)]
         unicode_offset: u128,
+
+        /// Deduplicate chains by endpoint so the table is "perfect" (one chain per endpoint)
+        #[arg(long, default_value_t = true)]
+        perfect: bool,
+
+        /// Use distinguished-point chains that stop once the hash's lowest N bits are zero,
+        /// instead of a fixed number of links (0 disables DP chains)
+        #[arg(long, default_value_t = 0)]
+        distinguished_bits: u8,
+
+        /// Maximum chain length before a distinguished-point chain is discarded as a runaway
+        #[arg(long, default_value_t = 10_000)]
+        max_chain_length: u64,
     },
 
     /// Dump rainbow table contents
@@ -89,6 +102,28 @@ enum Commands {
         in_file: PathBuf,
     },
 
+    /// Estimate rainbow table coverage / success probability
+    RainbowStats {
+        /// Existing rainbow table to read the chain count and merge rate from
+        #[arg(long)]
+        in_file: Option<PathBuf>,
+
+        #[arg(long, default_value_t = 95)]
+        charset_size: u128,
+
+        /// Required when --in-file is not given
+        #[arg(long)]
+        password_length: Option<usize>,
+
+        /// Required when --in-file is not given
+        #[arg(long)]
+        num_links: Option<usize>,
+
+        /// Required when --in-file is not given
+        #[arg(long)]
+        num_chains: Option<usize>,
+    },
+
     /// Crack hashes using rainbow table
     Crack {
         #[arg(long)]
@@ -102,6 +137,14 @@ enum Commands {
 
         #[arg(long)]
         hashes: PathBuf,
+
+        /// Exhaustively enumerate the keyspace for hashes the table misses
+        #[arg(long, default_value_t = false)]
+        brute_force: bool,
+
+        /// Caps the number of brute-force candidates tried (unbounded if omitted)
+        #[arg(long)]
+        max_candidates: Option<u128>,
     },
 }
 
@@ -136,6 +179,9 @@ fn main() -> Result<()> {
             in_file,
             charset_size,
             unicode_offset,
+            perfect,
+            distinguished_bits,
+            max_chain_length,
         } => rainbow::generate_rainbow_table(
             &in_file,
             &out_file,
@@ -144,6 +190,9 @@ fn main() -> Result<()> {
             threads,
             charset_size,
             unicode_offset,
+            perfect,
+            distinguished_bits,
+            max_chain_length,
         )
         .map_err(Into::into),
 
@@ -151,11 +200,36 @@ fn main() -> Result<()> {
             rainbow::dump_rainbow_table(&in_file).map_err(Into::into)
         }
 
+        Commands::RainbowStats {
+            in_file,
+            charset_size,
+            password_length,
+            num_links,
+            num_chains,
+        } => rainbow::report_coverage(
+            in_file.as_deref(),
+            charset_size,
+            password_length,
+            num_links,
+            num_chains,
+        )
+        .map_err(Into::into),
+
         Commands::Crack {
             in_file,
             out_file,
             threads,
             hashes,
-        } => rainbow::crack(&in_file, &hashes, out_file.as_deref(), threads).map_err(Into::into),
+            brute_force,
+            max_candidates,
+        } => rainbow::crack(
+            &in_file,
+            &hashes,
+            out_file.as_deref(),
+            threads,
+            brute_force,
+            max_candidates,
+        )
+        .map_err(Into::into),
     }
 }
@@ -14,16 +14,26 @@ use thiserror::Error;
 use tracing::{debug, error, info, instrument, warn};
 
 const MAGIC_WORD: &[u8] = b"rainbowtable";
-const VERSION: u8 = 1;
+const VERSION: u8 = 4;
 
-type RainbowTableData = (
-    Algorithm,
-    usize,
-    usize,
-    u128,
-    u128,
-    HashMap<Vec<u8>, Vec<u8>>,
-);
+/// A single generated chain: `(start password, endpoint password, chain length)`.
+type Chain = (Vec<u8>, Vec<u8>, u64);
+
+/// Parsed contents of an on-disk rainbow table file. `end_map` maps each
+/// chain's endpoint to its `(start, chain_length)`; for fixed-length chains
+/// `chain_length` is always `num_links`, for distinguished-point chains it
+/// varies per chain.
+struct ParsedRainbowTable {
+    algorithm: Algorithm,
+    password_length: usize,
+    num_links: usize,
+    charset_size: u128,
+    offset: u128,
+    perfect: bool,
+    distinguished_bits: u8,
+    max_chain_length: u64,
+    end_map: HashMap<Vec<u8>, (Vec<u8>, u64)>,
+}
 
 #[derive(Error, Debug)]
 pub enum RainbowError {
@@ -55,9 +65,12 @@ pub enum RainbowError {
     UnicodeError(String),
     #[error("Invalid numeric value: {0}")]
     NumericError(String),
+    #[error("Must provide --in-file, or all of --password-length, --num-links, and --num-chains")]
+    MissingStatsInputs,
 }
 
 #[instrument(skip_all)]
+#[allow(clippy::too_many_arguments)]
 pub fn generate_rainbow_table(
     input_path: &Path,
     output_path: &Path,
@@ -66,11 +79,14 @@ pub fn generate_rainbow_table(
     threads: usize,
     charset_size: u128,
     unicode_offset: u128,
+    perfect: bool,
+    distinguished_bits: u8,
+    max_chain_length: u64,
 ) -> Result<(), RainbowError> {
     info!("Generating rainbow table from: {}", input_path.display());
     debug!(
-        "Algorithm: {:?}, Links: {}, Threads: {}, Charset: {}, Offset: {}",
-        algorithm, num_links, threads, charset_size, unicode_offset
+        "Algorithm: {:?}, Links: {}, Threads: {}, Charset: {}, Offset: {}, Perfect: {}, DistinguishedBits: {}",
+        algorithm, num_links, threads, charset_size, unicode_offset, perfect, distinguished_bits
     );
 
     let passwords = crate::password::read_passwords(input_path)?;
@@ -96,21 +112,68 @@ pub fn generate_rainbow_table(
         .num_threads(threads)
         .build()?;
 
-    let chains: Vec<(Vec<u8>, Vec<u8>)> = pool.install(|| {
+    let raw_chains: Vec<Option<Chain>> = pool.install(|| {
         passwords
             .par_iter()
             .map(|start| -> Result<_, RainbowError> {
                 debug!("Processing chain for password: {}", start);
-                let mut current = start.clone();
-                for _ in 0..num_links {
-                    let hash = compute_hash_rainbow(&current, algorithm)?;
-                    current = reduce(&hash, password_length, charset_size, unicode_offset)?;
+                if distinguished_bits > 0 {
+                    generate_dp_chain(
+                        start,
+                        algorithm,
+                        password_length,
+                        charset_size,
+                        unicode_offset,
+                        distinguished_bits,
+                        max_chain_length,
+                    )
+                } else {
+                    let mut current = start.clone();
+                    for column in 0..num_links {
+                        let hash = compute_hash_rainbow(&current, algorithm)?;
+                        current =
+                            reduce(&hash, password_length, charset_size, unicode_offset, column)?;
+                    }
+                    Ok(Some((
+                        start.as_bytes().to_vec(),
+                        current.into_bytes(),
+                        num_links as u64,
+                    )))
                 }
-                Ok((start.as_bytes().to_vec(), current.into_bytes()))
             })
             .collect::<Result<Vec<_>, _>>()
     })?;
 
+    let discarded_for_length_cap = raw_chains.iter().filter(|c| c.is_none()).count();
+    if discarded_for_length_cap > 0 {
+        warn!(
+            "Discarded {} chain(s) exceeding max chain length {}",
+            discarded_for_length_cap, max_chain_length
+        );
+    }
+    let chains: Vec<Chain> = raw_chains.into_iter().flatten().collect();
+    let generated_chains = chains.len();
+
+    let chains = if perfect {
+        let mut by_endpoint: HashMap<Vec<u8>, (Vec<u8>, u64)> = HashMap::with_capacity(chains.len());
+        for (start, end, length) in chains {
+            by_endpoint.entry(end).or_insert((start, length));
+        }
+        let deduped: Vec<Chain> = by_endpoint
+            .into_iter()
+            .map(|(end, (start, length))| (start, end, length))
+            .collect();
+        let merged = generated_chains - deduped.len();
+        info!(
+            "Discarded {} merged chain(s), keeping {} perfect chain(s)",
+            merged,
+            deduped.len()
+        );
+        deduped
+    } else {
+        chains
+    };
+
     let mut file = File::create(output_path)?;
     write_header(
         &mut file,
@@ -119,23 +182,39 @@ pub fn generate_rainbow_table(
         charset_size,
         num_links,
         unicode_offset,
+        perfect,
+        distinguished_bits,
+        max_chain_length as u128,
     )?;
 
     let num_chains = chains.len();
+    let mut total_links = 0u64;
 
-    for (start, end) in &chains {
+    for (start, end, length) in &chains {
         file.write_all(start)?;
         file.write_all(end)?;
+        file.write_all(&length.to_be_bytes())?;
+        total_links += length;
     }
 
+    let header_size = header_size(algorithm);
+    let file_size = header_size + num_chains * chain_record_size(password_length);
+    let coverage_per_byte = total_links as f64 / file_size as f64;
     info!(
-        "Successfully generated rainbow table with {} chains",
-        num_chains
+        "Successfully generated rainbow table with {} chains ({:.4} covered passwords/byte)",
+        num_chains, coverage_per_byte
     );
 
     Ok(())
 }
 
+/// Size in bytes of a single on-disk chain record: start password, endpoint
+/// password, and an 8-byte chain length (always stored so fixed-length and
+/// distinguished-point chains share one record format).
+fn chain_record_size(password_length: usize) -> usize {
+    2 * password_length + 8
+}
+
 fn compute_hash_rainbow(password: &str, algorithm: Algorithm) -> Result<Vec<u8>, RainbowError> {
     crate::hashing::compute_hash(password, algorithm).map_err(|e| match e {
         HashError::Scrypt(_) => RainbowError::InvalidAlgorithm,
@@ -146,11 +225,16 @@ fn compute_hash_rainbow(password: &str, algorithm: Algorithm) -> Result<Vec<u8>,
     })
 }
 
+/// Reduces a hash to a candidate password, using `column` to select a distinct
+/// reduction function per chain link so that chains colliding at different
+/// columns don't merge into the same family (true rainbow chaining, as
+/// opposed to Hellman chains which reuse one reduction everywhere).
 fn reduce(
     hash: &[u8],
     password_length: usize,
     charset_size: u128,
     offset: u128,
+    column: usize,
 ) -> Result<String, RainbowError> {
     let mut password = String::with_capacity(password_length);
     let mut hash_cycle = hash.iter().cycle();
@@ -164,7 +248,7 @@ fn reduce(
                 .wrapping_add(*hash_cycle.next().unwrap_or(&0) as u128);
         }
 
-        code_point = offset + (code_point % charset_size);
+        code_point = offset + (code_point.wrapping_add(column as u128) % charset_size);
 
         let c = char::from_u32(code_point as u32).ok_or_else(|| {
             RainbowError::UnicodeError(format!("Invalid Unicode code point: {}", code_point))
@@ -183,6 +267,60 @@ fn reduce(
     Ok(password)
 }
 
+/// Generates a distinguished-point chain. Unlike fixed-length chains, a DP
+/// chain always uses the column-0 reduction: cracking walks forward from a
+/// target hash of unknown original position, so every link must use the
+/// same reduction for a single `end_map` lookup to be able to find it.
+/// Returns `Ok(None)` if `max_chain_length` is exceeded without hitting a
+/// distinguished hash, so the caller can discard the runaway chain.
+fn generate_dp_chain(
+    start: &str,
+    algorithm: Algorithm,
+    password_length: usize,
+    charset_size: u128,
+    offset: u128,
+    distinguished_bits: u8,
+    max_chain_length: u64,
+) -> Result<Option<Chain>, RainbowError> {
+    let mut current = start.to_string();
+
+    for length in 1..=max_chain_length {
+        let hash = compute_hash_rainbow(&current, algorithm)?;
+        current = reduce(&hash, password_length, charset_size, offset, 0)?;
+        if is_distinguished(&hash, distinguished_bits) {
+            return Ok(Some((start.as_bytes().to_vec(), current.into_bytes(), length)));
+        }
+    }
+
+    Ok(None)
+}
+
+/// True if `hash`'s lowest `bits` bits are all zero — the distinguishing
+/// property that marks the end of a DP chain.
+fn is_distinguished(hash: &[u8], bits: u8) -> bool {
+    let mut remaining = bits as usize;
+
+    for &byte in hash.iter().rev() {
+        if remaining == 0 {
+            break;
+        } else if remaining >= 8 {
+            if byte != 0 {
+                return false;
+            }
+            remaining -= 8;
+        } else {
+            let mask = (1u8 << remaining) - 1;
+            if byte & mask != 0 {
+                return false;
+            }
+            remaining = 0;
+        }
+    }
+
+    remaining == 0
+}
+
+#[allow(clippy::too_many_arguments)]
 fn write_header(
     file: &mut File,
     algorithm: Algorithm,
@@ -190,6 +328,9 @@ fn write_header(
     charset_size: u128,
     num_links: usize,
     offset: u128,
+    perfect: bool,
+    distinguished_bits: u8,
+    max_chain_length: u128,
 ) -> Result<(), RainbowError> {
     if password_length > u8::MAX as usize {
         return Err(RainbowError::InvalidHeader);
@@ -206,40 +347,54 @@ fn write_header(
     file.write_all(&charset_size.to_be_bytes())?;
     file.write_all(&(num_links as u128).to_be_bytes())?;
     file.write_all(&offset.to_be_bytes())?;
+    file.write_all(&[perfect as u8])?;
+    file.write_all(&[distinguished_bits])?;
+    file.write_all(&max_chain_length.to_be_bytes())?;
 
     Ok(())
 }
 
+/// Size in bytes of the fixed-format header for the given algorithm.
+fn header_size(algorithm: Algorithm) -> usize {
+    MAGIC_WORD.len() + 1 + 1 + algorithm.to_string().len() + 1 + 16 + 16 + 16 + 1 + 1 + 16
+}
+
 #[instrument(skip_all)]
 pub fn dump_rainbow_table(path: &Path) -> Result<(), RainbowError> {
     info!("Dumping rainbow table from: {}", path.display());
-    let (algorithm, pw_len, num_links, charset_size, offset, _) = parse_rainbow(path)?;
+    let table = parse_rainbow(path)?;
 
     println!("Hashassin Rainbow Table");
     println!("VERSION: {}", VERSION);
-    println!("ALGORITHM: {}", algorithm);
-    println!("PASSWORD LENGTH: {}", pw_len);
-    println!("CHAR SET SIZE: {}", charset_size);
-    println!("NUM LINKS: {}", num_links);
-    println!("ASCII OFFSET: {}", offset);
+    println!("ALGORITHM: {}", table.algorithm);
+    println!("PASSWORD LENGTH: {}", table.password_length);
+    println!("CHAR SET SIZE: {}", table.charset_size);
+    println!("NUM LINKS: {}", table.num_links);
+    println!("ASCII OFFSET: {}", table.offset);
+    println!("PERFECT: {}", table.perfect);
+    println!("DISTINGUISHED BITS: {}", table.distinguished_bits);
+    println!("MAX CHAIN LENGTH: {}", table.max_chain_length);
 
     let mut file = File::open(path)?;
     let mut buffer = Vec::new();
     file.read_to_end(&mut buffer)?;
 
-    let header_size = MAGIC_WORD.len() + 1 + 1 + algorithm.to_string().len() + 1 + 16 + 16 + 16;
-    let chain_size = 2 * pw_len;
+    let header_size = header_size(table.algorithm);
+    let chain_size = chain_record_size(table.password_length);
 
     for chunk in buffer[header_size..].chunks(chain_size) {
         if chunk.len() != chain_size {
             warn!("Invalid chain data length: {}", chunk.len());
             break;
         }
-        let (start, end) = chunk.split_at(pw_len);
+        let (start, rest) = chunk.split_at(table.password_length);
+        let (end, length_bytes) = rest.split_at(table.password_length);
+        let length = u64::from_be_bytes(length_bytes.try_into().unwrap_or([0; 8]));
         println!(
-            "{}\t{}",
+            "{}\t{}\t{}",
             String::from_utf8_lossy(start),
-            String::from_utf8_lossy(end)
+            String::from_utf8_lossy(end),
+            length
         );
     }
 
@@ -247,12 +402,175 @@ pub fn dump_rainbow_table(path: &Path) -> Result<(), RainbowError> {
     Ok(())
 }
 
+/// Coverage statistics for a rainbow table: keyspace size, expected success
+/// probability, and expected number of distinct passwords covered.
+#[derive(Debug, Clone, Copy)]
+pub struct CoverageStats {
+    pub keyspace: u128,
+    pub success_probability: f64,
+    pub expected_covered: f64,
+}
+
+/// Estimates the fraction of the keyspace `num_chains` chains of `num_links`
+/// links are expected to cover, using the standard rainbow-table
+/// approximation `P ~= 1 - exp(-(m * num_links) / N)`, where `N` is the
+/// keyspace `charset_size ^ password_length`.
+pub fn estimate_coverage(
+    charset_size: u128,
+    password_length: usize,
+    num_links: usize,
+    num_chains: usize,
+) -> CoverageStats {
+    let keyspace = charset_size.saturating_pow(password_length as u32);
+    let keyspace_f64 = keyspace as f64;
+    let covered = num_chains as f64 * num_links as f64;
+
+    let success_probability = if keyspace_f64 > 0.0 {
+        1.0 - (-covered / keyspace_f64).exp()
+    } else {
+        0.0
+    };
+    let expected_covered = keyspace_f64 * success_probability;
+
+    CoverageStats {
+        keyspace,
+        success_probability,
+        expected_covered,
+    }
+}
+
+/// Metadata read back from an on-disk rainbow table, used for coverage
+/// reporting and diagnostics. `num_links` is the average observed chain
+/// length, which equals the header's fixed link count for classic tables
+/// and the mean DP chain length for distinguished-point tables.
+#[derive(Debug, Clone, Copy)]
+pub struct TableStats {
+    pub charset_size: u128,
+    pub password_length: usize,
+    pub num_links: usize,
+    pub perfect: bool,
+    pub distinguished_bits: u8,
+    pub num_chains: usize,
+    pub distinct_endpoints: usize,
+}
+
+fn table_stats(path: &Path) -> Result<TableStats, RainbowError> {
+    let table = parse_rainbow(path)?;
+
+    let mut file = File::open(path)?;
+    let mut buffer = Vec::new();
+    file.read_to_end(&mut buffer)?;
+
+    let header_size = header_size(table.algorithm);
+    let chain_size = chain_record_size(table.password_length);
+    let num_chains = buffer[header_size..]
+        .chunks(chain_size)
+        .filter(|chunk| chunk.len() == chain_size)
+        .count();
+
+    let avg_links = if table.end_map.is_empty() {
+        table.num_links
+    } else {
+        let total_links: u64 = table.end_map.values().map(|(_, length)| *length).sum();
+        (total_links / table.end_map.len() as u64) as usize
+    };
+
+    Ok(TableStats {
+        charset_size: table.charset_size,
+        password_length: table.password_length,
+        num_links: avg_links,
+        perfect: table.perfect,
+        distinguished_bits: table.distinguished_bits,
+        num_chains,
+        distinct_endpoints: table.end_map.len(),
+    })
+}
+
+/// Reports the estimated success probability and expected coverage for a
+/// rainbow table, either from explicit parameters or by reading an existing
+/// table file (in which case the observed merge/duplicate-endpoint rate is
+/// reported alongside the estimate).
+#[instrument(skip_all)]
+pub fn report_coverage(
+    in_file: Option<&Path>,
+    charset_size: u128,
+    password_length: Option<usize>,
+    num_links: Option<usize>,
+    num_chains: Option<usize>,
+) -> Result<(), RainbowError> {
+    let (charset_size, password_length, num_links, num_chains, merge_stats) = match in_file {
+        Some(path) => {
+            info!("Reading chain count from table: {}", path.display());
+            let stats = table_stats(path)?;
+            let merged = stats.num_chains.saturating_sub(stats.distinct_endpoints);
+            let merge_rate = if stats.num_chains > 0 {
+                merged as f64 / stats.num_chains as f64
+            } else {
+                0.0
+            };
+            (
+                stats.charset_size,
+                stats.password_length,
+                stats.num_links,
+                stats.num_chains,
+                Some((stats.perfect, stats.distinguished_bits, merged, merge_rate)),
+            )
+        }
+        None => {
+            let password_length = password_length.ok_or(RainbowError::MissingStatsInputs)?;
+            let num_links = num_links.ok_or(RainbowError::MissingStatsInputs)?;
+            let num_chains = num_chains.ok_or(RainbowError::MissingStatsInputs)?;
+            (charset_size, password_length, num_links, num_chains, None)
+        }
+    };
+
+    let stats = estimate_coverage(charset_size, password_length, num_links, num_chains);
+
+    println!("Hashassin Rainbow Table Coverage Estimate");
+    println!("CHAR SET SIZE: {}", charset_size);
+    println!("PASSWORD LENGTH: {}", password_length);
+    println!("NUM LINKS: {}", num_links);
+    println!("NUM CHAINS: {}", num_chains);
+    println!("KEYSPACE: {}", stats.keyspace);
+    println!("SUCCESS PROBABILITY: {:.6}", stats.success_probability);
+    println!("EXPECTED PASSWORDS COVERED: {:.0}", stats.expected_covered);
+
+    if let Some((perfect, distinguished_bits, merged, merge_rate)) = merge_stats {
+        println!("PERFECT: {}", perfect);
+        println!("DISTINGUISHED BITS: {}", distinguished_bits);
+        println!("OBSERVED MERGED CHAINS: {}", merged);
+        println!("OBSERVED MERGE RATE: {:.6}", merge_rate);
+    }
+
+    Ok(())
+}
+
+/// Where a cracked password came from, so output can distinguish table hits
+/// from the (much more expensive) brute-force fallback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CrackSource {
+    Table,
+    BruteForce,
+}
+
+impl std::fmt::Display for CrackSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CrackSource::Table => write!(f, "table"),
+            CrackSource::BruteForce => write!(f, "brute-force"),
+        }
+    }
+}
+
 #[instrument(skip_all)]
+#[allow(clippy::too_many_arguments)]
 pub fn crack(
     rainbow_path: &Path,
     hash_path: &Path,
     output_path: Option<&Path>,
     threads: usize,
+    brute_force: bool,
+    max_candidates: Option<u128>,
 ) -> Result<(), RainbowError> {
     info!(
         "Cracking hashes from {} using rainbow table {}",
@@ -260,60 +578,101 @@ pub fn crack(
         rainbow_path.display()
     );
 
-    let (rainbow_algorithm, pw_len, num_links, charset_size, offset, end_map) =
-        parse_rainbow(rainbow_path)?;
+    let table = parse_rainbow(rainbow_path)?;
     let (hash_algorithm, hashes) = parse_hash_file(hash_path)?;
 
-    if rainbow_algorithm != hash_algorithm {
+    if table.algorithm != hash_algorithm {
         return Err(RainbowError::AlgorithmMismatch);
     }
+    let rainbow_algorithm = table.algorithm;
+    let pw_len = table.password_length;
+    let charset_size = table.charset_size;
+    let offset = table.offset;
 
     let pool = rayon::ThreadPoolBuilder::new()
         .num_threads(threads)
         .build()?;
 
-    let results: Vec<_> = pool.install(|| {
+    let table_results: Vec<_> = pool.install(|| {
         hashes
             .par_iter()
             .filter_map(|target_hash| {
-                (0..num_links).find_map(|i| {
-                    let mut current_hash = target_hash.clone();
-                    for _ in 0..i {
-                        let password = match reduce(&current_hash, pw_len, charset_size, offset) {
-                            Ok(p) => p,
-                            Err(_) => return None,
-                        };
-
-                        current_hash = match compute_hash_rainbow(&password, rainbow_algorithm) {
-                            Ok(h) => h,
-                            Err(_) => return None,
-                        };
-                    }
-
-                    let possible_end = match reduce(&current_hash, pw_len, charset_size, offset) {
-                        Ok(p) => p,
-                        Err(_) => return None,
-                    };
-
-                    end_map.get(possible_end.as_bytes()).and_then(|start| {
-                        match regenerate_chain(
-                            start,
-                            target_hash,
-                            num_links - i,
-                            rainbow_algorithm,
-                            pw_len,
-                            charset_size,
-                            offset,
-                        ) {
-                            Ok(Some(res)) => Some(res),
-                            _ => None,
-                        }
-                    })
-                })
+                if table.distinguished_bits > 0 {
+                    crack_dp(
+                        target_hash,
+                        rainbow_algorithm,
+                        pw_len,
+                        charset_size,
+                        offset,
+                        table.distinguished_bits,
+                        table.max_chain_length,
+                        &table.end_map,
+                    )
+                } else {
+                    crack_fixed(
+                        target_hash,
+                        rainbow_algorithm,
+                        pw_len,
+                        table.num_links,
+                        charset_size,
+                        offset,
+                        &table.end_map,
+                    )
+                }
             })
             .collect()
     });
 
+    let solved: std::collections::HashSet<Vec<u8>> =
+        table_results.iter().map(|(hash, _)| hash.clone()).collect();
+
+    let mut results: Vec<(Vec<u8>, String, CrackSource)> = table_results
+        .into_iter()
+        .map(|(hash, password)| (hash, password, CrackSource::Table))
+        .collect();
+
+    if brute_force {
+        let unsolved: std::collections::HashSet<Vec<u8>> = hashes
+            .iter()
+            .filter(|hash| !solved.contains(*hash))
+            .cloned()
+            .collect();
+
+        if !unsolved.is_empty() {
+            let keyspace = charset_size.saturating_pow(pw_len as u32);
+            let candidate_count = match max_candidates {
+                Some(cap) => keyspace.min(cap),
+                None => keyspace,
+            }
+            .min(u64::MAX as u128) as u64;
+
+            info!(
+                "Brute-forcing {} unsolved hash(es) over {} candidate(s)",
+                unsolved.len(),
+                candidate_count
+            );
+
+            let brute_force_results: Vec<_> = pool.install(|| {
+                (0..candidate_count)
+                    .into_par_iter()
+                    .filter_map(|index| {
+                        let password =
+                            candidate_from_index(index as u128, pw_len, charset_size, offset)
+                                .ok()?;
+                        let hash = compute_hash_rainbow(&password, rainbow_algorithm).ok()?;
+                        unsolved.contains(&hash).then_some((hash, password))
+                    })
+                    .collect()
+            });
+
+            results.extend(
+                brute_force_results
+                    .into_iter()
+                    .map(|(hash, password)| (hash, password, CrackSource::BruteForce)),
+            );
+        }
+    }
+
     if results.is_empty() {
         return Err(RainbowError::NoPasswordsFound);
     }
@@ -323,14 +682,113 @@ pub fn crack(
         None => Box::new(io::stdout()),
     };
 
-    for (hash, password) in &results {
-        writeln!(writer, "{}\t{}", hex::encode(hash), password)?;
+    for (hash, password, source) in &results {
+        writeln!(writer, "{}\t{}\t{}", hex::encode(hash), password, source)?;
     }
 
     Ok(())
 }
 
-fn parse_rainbow(path: &Path) -> Result<RainbowTableData, RainbowError> {
+/// Looks up a target hash in a fixed-length table by guessing every possible
+/// column it could sit at, walking forward to the chain's endpoint for each
+/// guess, and checking `end_map`.
+#[allow(clippy::too_many_arguments)]
+fn crack_fixed(
+    target_hash: &[u8],
+    algorithm: Algorithm,
+    pw_len: usize,
+    num_links: usize,
+    charset_size: u128,
+    offset: u128,
+    end_map: &HashMap<Vec<u8>, (Vec<u8>, u64)>,
+) -> Option<(Vec<u8>, String)> {
+    (0..num_links).find_map(|i| {
+        // Assume target_hash sits at column `num_links - 1 - i`; walk the
+        // remaining columns forward to the end of the chain.
+        let start_column = num_links - 1 - i;
+        let mut current_hash = target_hash.to_vec();
+        for column in start_column..start_column + i {
+            let password = reduce(&current_hash, pw_len, charset_size, offset, column).ok()?;
+            current_hash = compute_hash_rainbow(&password, algorithm).ok()?;
+        }
+
+        let possible_end = reduce(&current_hash, pw_len, charset_size, offset, num_links - 1).ok()?;
+        let (start, _) = end_map.get(possible_end.as_bytes())?;
+        regenerate_chain(start, target_hash, num_links - i, algorithm, pw_len, charset_size, offset, None)
+            .ok()
+            .flatten()
+    })
+}
+
+/// Looks up a target hash in a distinguished-point table by walking forward
+/// with the single DP reduction (column 0) until a distinguished hash is
+/// reached, then doing one `end_map` lookup — no column guessing needed,
+/// since every link uses the same reduction regardless of position.
+#[allow(clippy::too_many_arguments)]
+fn crack_dp(
+    target_hash: &[u8],
+    algorithm: Algorithm,
+    pw_len: usize,
+    charset_size: u128,
+    offset: u128,
+    distinguished_bits: u8,
+    max_chain_length: u64,
+    end_map: &HashMap<Vec<u8>, (Vec<u8>, u64)>,
+) -> Option<(Vec<u8>, String)> {
+    let mut current_hash = target_hash.to_vec();
+
+    for _ in 0..max_chain_length {
+        if is_distinguished(&current_hash, distinguished_bits) {
+            let possible_end = reduce(&current_hash, pw_len, charset_size, offset, 0).ok()?;
+            let (start, length) = end_map.get(possible_end.as_bytes())?;
+            return regenerate_chain(
+                start,
+                target_hash,
+                *length as usize,
+                algorithm,
+                pw_len,
+                charset_size,
+                offset,
+                Some(0),
+            )
+            .ok()
+            .flatten();
+        }
+
+        let password = reduce(&current_hash, pw_len, charset_size, offset, 0).ok()?;
+        current_hash = compute_hash_rainbow(&password, algorithm).ok()?;
+    }
+
+    None
+}
+
+/// Maps an index in `0..charset_size^password_length` to the password it
+/// represents, using the same ASCII range (`offset..offset+charset_size`)
+/// that `reduce` emits, so brute-force enumerates the identical keyspace.
+fn candidate_from_index(
+    mut index: u128,
+    password_length: usize,
+    charset_size: u128,
+    offset: u128,
+) -> Result<String, RainbowError> {
+    let mut digits = vec![0u128; password_length];
+    for digit in digits.iter_mut().rev() {
+        *digit = index % charset_size;
+        index /= charset_size;
+    }
+
+    digits
+        .into_iter()
+        .map(|digit| {
+            let code_point = offset + digit;
+            char::from_u32(code_point as u32).ok_or_else(|| {
+                RainbowError::UnicodeError(format!("Invalid Unicode code point: {}", code_point))
+            })
+        })
+        .collect()
+}
+
+fn parse_rainbow(path: &Path) -> Result<ParsedRainbowTable, RainbowError> {
     let mut file = File::open(path)?;
     let mut buffer = Vec::new();
     file.read_to_end(&mut buffer)?;
@@ -378,28 +836,53 @@ fn parse_rainbow(path: &Path) -> Result<RainbowTableData, RainbowError> {
     );
     cursor += 16;
 
+    let perfect = *buffer.get(cursor).ok_or(RainbowError::InvalidHeader)? != 0;
+    cursor += 1;
+
+    let distinguished_bits = *buffer.get(cursor).ok_or(RainbowError::InvalidHeader)?;
+    cursor += 1;
+
+    let max_chain_length = u128::from_be_bytes(
+        buffer[cursor..cursor + 16]
+            .try_into()
+            .map_err(|_| RainbowError::InvalidHeader)?,
+    ) as u64;
+    cursor += 16;
+
     let mut end_map = HashMap::new();
-    let chain_size = 2 * password_length;
+    let chain_size = chain_record_size(password_length);
     for chunk in buffer[cursor..].chunks(chain_size) {
         if chunk.len() != chain_size {
             break;
         }
-        let (start, end) = chunk.split_at(password_length);
-        end_map.insert(end.to_vec(), start.to_vec());
+        let (start, rest) = chunk.split_at(password_length);
+        let (end, length_bytes) = rest.split_at(password_length);
+        let length = u64::from_be_bytes(length_bytes.try_into().unwrap_or([0; 8]));
+        end_map.insert(end.to_vec(), (start.to_vec(), length));
     }
 
     let algorithm = Algorithm::from_str(algorithm_str).ok_or(RainbowError::InvalidAlgorithm)?;
 
-    Ok((
+    Ok(ParsedRainbowTable {
         algorithm,
         password_length,
         num_links,
         charset_size,
         offset,
+        perfect,
+        distinguished_bits,
+        max_chain_length,
         end_map,
-    ))
+    })
 }
 
+/// Replays a chain from its stored `start` for `steps` reductions, checking
+/// whether `target_hash` is produced along the way. `fixed_column` selects
+/// the column used for every reduction: `None` means a fixed-length chain,
+/// where the column advances with each step (mirroring generation); `Some(c)`
+/// pins every reduction to column `c`, used for distinguished-point chains,
+/// which only ever use the column-0 reduction.
+#[allow(clippy::too_many_arguments)]
 fn regenerate_chain(
     start: &[u8],
     target_hash: &[u8],
@@ -408,16 +891,18 @@ fn regenerate_chain(
     password_length: usize,
     charset_size: u128,
     offset: u128,
+    fixed_column: Option<usize>,
 ) -> Result<Option<(Vec<u8>, String)>, RainbowError> {
     let mut current =
         String::from_utf8(start.to_vec()).map_err(|e| RainbowError::UnicodeError(e.to_string()))?;
 
-    for _ in 0..steps {
+    for step in 0..steps {
         let hash = compute_hash_rainbow(&current, algorithm)?;
         if hash == target_hash {
             return Ok(Some((target_hash.to_vec(), current)));
         }
-        current = reduce(&hash, password_length, charset_size, offset)?;
+        let column = fixed_column.unwrap_or(step);
+        current = reduce(&hash, password_length, charset_size, offset, column)?;
     }
 
     Ok(None)